@@ -0,0 +1,108 @@
+use std::io;
+
+/// Reads sub-byte, MSB-first bit-fields out of a byte stream. Used by `#[derive(structurs::Read)]`
+/// to implement `#[bits = N]` fields; see [`structurs_derive`].
+pub struct BitReader<'r, R>
+{
+  reader: &'r mut R,
+  buf: u8,
+  bits_left: u32,
+}
+
+impl<'r, R> BitReader<'r, R>
+where
+  R: io::Read,
+{
+  #[inline]
+  pub fn new(reader: &'r mut R) -> Self
+  {
+    Self {
+      reader,
+      buf: 0,
+      bits_left: 0,
+    }
+  }
+
+  /// Reads `bits` bits (`bits` must be at most 64) and returns them right-aligned in a `u64`, most
+  /// significant bit of the stream first.
+  pub fn read_bits(&mut self, bits: u32) -> io::Result<u64>
+  {
+    let mut value: u64 = 0;
+    let mut remaining = bits;
+    while remaining > 0 {
+      if self.bits_left == 0 {
+        let mut byte = [0u8; 1];
+        self.reader.read_exact(&mut byte)?;
+        self.buf = byte[0];
+        self.bits_left = 8;
+      }
+      let take = remaining.min(self.bits_left);
+      let shift = self.bits_left - take;
+      let mask = ((1u16 << take) - 1) as u8;
+      let chunk = (self.buf >> shift) & mask;
+      value = (value << take) | chunk as u64;
+      self.bits_left -= take;
+      remaining -= take;
+    }
+    Ok(value)
+  }
+}
+
+/// Writes sub-byte, MSB-first bit-fields into a byte stream. Used by `#[derive(structurs::Write)]`
+/// to implement `#[bits = N]` fields; see [`structurs_derive`]. Call [`BitWriter::finish`] once the
+/// last bit-field of a run has been written to flush any partial byte, zero-padded.
+pub struct BitWriter<'w, W>
+{
+  writer: &'w mut W,
+  buf: u8,
+  bits_filled: u32,
+}
+
+impl<'w, W> BitWriter<'w, W>
+where
+  W: io::Write,
+{
+  #[inline]
+  pub fn new(writer: &'w mut W) -> Self
+  {
+    Self {
+      writer,
+      buf: 0,
+      bits_filled: 0,
+    }
+  }
+
+  /// Writes the low `bits` bits of `value` (`bits` must be at most 64), most significant bit of
+  /// `value` first.
+  pub fn write_bits(&mut self, value: u64, bits: u32) -> io::Result<()>
+  {
+    let mut remaining = bits;
+    while remaining > 0 {
+      let space = 8 - self.bits_filled;
+      let take = remaining.min(space);
+      let shift = remaining - take;
+      let chunk = ((value >> shift) & ((1u64 << take) - 1)) as u8;
+      self.buf |= chunk << (space - take);
+      self.bits_filled += take;
+      remaining -= take;
+      if self.bits_filled == 8 {
+        self.writer.write_all(&[self.buf])?;
+        self.buf = 0;
+        self.bits_filled = 0;
+      }
+    }
+    Ok(())
+  }
+
+  /// Flushes a partial byte, if any, zero-padded on the low bits.
+  #[inline]
+  pub fn finish(mut self) -> io::Result<()>
+  {
+    if self.bits_filled > 0 {
+      self.writer.write_all(&[self.buf])?;
+      self.buf = 0;
+      self.bits_filled = 0;
+    }
+    Ok(())
+  }
+}
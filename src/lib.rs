@@ -10,16 +10,18 @@
 //!
 //! ```edition2018, ignore
 //! #[derive(structurs::Read)]
+//! #[derive(structurs::Write)]
 //! ```
 //!
-//! Writing a function that reads bytes into a structure can be a tedious job.
-//! `#[derive(structurs::Read)]` macro automatically generates code that implements this trait for
-//! your structure. It also includes attributes that can change the way some of the fields are
-//! read.
+//! Writing a function that reads or writes bytes for a structure can be a tedious job.
+//! `#[derive(structurs::Read)]` and `#[derive(structurs::Write)]` macros automatically generate
+//! code that implements these traits for your structure. They share the same set of attributes
+//! that can change the way some of the fields are read or written.
 //!
 //! By default all fields will be read using [`structurs::Read::read`] function, but you might have
 //! fields that might need to be read in big-endian format. In that case you can mark those fields
-//! with `#[be]` attribute.
+//! with `#[be]` attribute. `#[derive(structurs::Write)]` honors the same attribute when writing the
+//! field back out.
 //!
 //! ## Attributes
 //!
@@ -29,11 +31,42 @@
 //! - `#[be]`, This denotes that the field is in big-endian format.
 //! - `#[ne]`, This denotes that the field is in CPU's native endian format. Most CPU's will use
 //!   little-endian format.
-//! - `#[pad]`, This denotes that the field is a padding and is not important. In this case the
-//!   field will be initialized to its default value using [`Default::default`]. By default the
-//!   length of the field type worth of bytes will be read from the reader. You can also pass a
-//!   `bytes` value to this attribute. `#[pad(bytes = N)]` means that N bytes should be read from
-//!   the reader in which case field type is not important and should be [`structurs::Pad`].
+//! - `#[pad]`, This denotes that the field is a padding and is not important. When reading, the
+//!   field will be initialized to its default value using [`Default::default`]. When writing, N
+//!   zero bytes are emitted instead of serializing the field. By default the length of the field
+//!   type worth of bytes will be read or written. You can also pass a `bytes` value to this
+//!   attribute. `#[pad(bytes = N)]` means that N bytes should be read from the reader (or written
+//!   to the writer) in which case field type is not important and should be [`structurs::Pad`].
+//! - `#[count(<expr>)]`, This can be used on a field of type `Vec<T>` to read/write a
+//!   variable-length sequence: `<expr>` is evaluated as the number of elements, reading that many
+//!   `T`s into the `Vec`, and may refer to an earlier field of the same struct by name (e.g.
+//!   `#[count(header_len)]`). Writing emits exactly `vec.len()` elements; the count itself is
+//!   whatever the earlier field already wrote.
+//! - `#[endian]`, This denotes that the field's byte order is chosen at runtime rather than fixed
+//!   by `#[le]`/`#[be]`/`#[ne]`. A type deriving `structurs::Read` with an `#[endian]` field also
+//!   gets a [`structurs::Read::read_with_endian`] that takes a [`structurs::Endian`] argument and
+//!   threads it through to that field via [`structurs::PrimitiveRead::read_endian`]. Plain
+//!   [`structurs::Read::read`] reads such a field in native-endian.
+//! - `#[read_if(<expr>)]`, This can be used on a field of type `Option<T>` to read/write it
+//!   conditionally: `<expr>` is evaluated (and may refer to an earlier field of the same struct by
+//!   name) and, if true, a `T` is read and the field becomes `Some`; otherwise no bytes are
+//!   consumed and the field is `None`. Writing mirrors this: `Some` serializes the inner value,
+//!   `None` writes nothing.
+//! - `#[align = <expr>]`, `#[seek_before = <expr>]`, `#[pad_after = <expr>]`, These reposition the
+//!   stream directly instead of reading or writing bytes, and are only honored by
+//!   [`structurs::Read::read_seek`]/[`structurs::Write::write_seek`], which require the underlying
+//!   reader or writer to also implement [`std::io::Seek`]. `#[align = N]` seeks forward to the
+//!   next multiple of `N` bytes from the start of the stream; `#[seek_before = <offset>]` seeks to
+//!   the absolute `<offset>`; `#[pad_after = N]` seeks forward `N` bytes. They are typically used
+//!   on a [`structurs::Pad`]-typed field in place of `#[pad]`. Plain `read`/`write` (no `Seek`
+//!   bound available) can't reposition the stream, so a type with a seek directive field returns
+//!   an [`std::io::Error`] from `read`/`write` instead; use `read_seek`/`write_seek`.
+//! - `#[bits = <expr>]`, This reads/writes an integer field as `<expr>` bits rather than whole
+//!   bytes, MSB-first, packing a run of consecutive `#[bits]` fields into the same underlying
+//!   bytes via [`structurs::BitReader`]/[`structurs::BitWriter`] and aligning to the next byte
+//!   boundary once the run ends. It is a runtime error for `<expr>` to exceed the field's type's
+//!   bit width, or 64, whichever is smaller (both bit readers and writers accumulate into a
+//!   `u64`).
 //!
 //! ## Example
 //!
@@ -52,16 +85,58 @@
 //! }
 //! ```
 //!
+//! ## Tagged enums
+//!
+//! Both macros also support enums whose variant is selected by a leading tag value, such as a
+//! message frame whose type is determined by a leading opcode. Mark the enum with `#[tag(T)]`
+//! where `T` is the primitive type of the tag (honoring `#[le]`/`#[be]`/`#[ne]` on the enum
+//! itself), and mark each variant with `#[tag_value = N]`.
+//!
+//! ```
+//! #[derive(structurs::Read, structurs::Write)]
+//! #[tag(u16)]
+//! #[be]
+//! enum Frame
+//! {
+//!   #[tag_value = 0]
+//!   Ping
+//!   {
+//!     payload: u32,
+//!   },
+//!   #[tag_value = 1]
+//!   Pong
+//!   {
+//!     payload: u32,
+//!   },
+//! }
+//! ```
+//!
+//! Reading an unrecognized tag value produces an [`std::io::Error`] with
+//! [`std::io::ErrorKind::InvalidData`].
+//!
+//! ## Static size
+//!
+//! `#[derive(structurs::StaticSize)]` implements [`structurs::StaticSize`], which exposes the
+//! type's serialized size in bytes as the associated constant `SIZE: Option<usize>`, computed by
+//! summing the size of each field (recursing into nested types that also derive `StaticSize`) at
+//! compile time. `SIZE` is `None` for a type with a field whose length isn't known ahead of time,
+//! such as a `#[count(<expr>)]` field.
+//!
 //! ## Note
 //!
-//! This macro currently only supports structs with named fields.
+//! This macro currently only supports structs with named fields and tagged enums whose variants
+//! have named fields.
 
 use std::io;
 
+mod bits;
 mod read;
+mod static_size;
 mod write;
 
-pub use read::{PrimitiveRead, Read, Reader};
+pub use bits::{BitReader, BitWriter};
+pub use read::{Endian, PrimitiveRead, Read, Reader};
+pub use static_size::{static_size_add, static_size_agree, static_size_mul, StaticSize};
 pub use write::{PrimitiveWrite, Write, Writer};
 
 #[cfg(feature = "derive")]
@@ -71,6 +146,11 @@ pub use structurs_derive::*;
 #[derive(Debug, Default, Eq, PartialEq, Copy, Clone)]
 pub struct Pad;
 
+impl StaticSize for Pad
+{
+  const SIZE: Option<usize> = Some(0);
+}
+
 macro_rules! impl_primitive {
   ($ty:ty, $bytes:expr) => {
     impl PrimitiveRead for $ty
@@ -138,6 +218,11 @@ macro_rules! impl_primitive {
         self.write_ne(writer)
       }
     }
+
+    impl StaticSize for $ty
+    {
+      const SIZE: Option<usize> = Some($bytes);
+    }
   };
 }
 
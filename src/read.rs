@@ -55,10 +55,29 @@ pub trait Reader: io::Read
   {
     T::read_be(self)
   }
+
+  #[inline]
+  fn read_seek<T>(&mut self) -> io::Result<T>
+  where
+    T: Read,
+    Self: Sized + io::Seek,
+  {
+    T::read_seek(self)
+  }
 }
 
 impl<T> Reader for T where T: io::Read {}
 
+/// Byte order chosen at runtime, as opposed to the `#[le]`/`#[be]`/`#[ne]` attributes which fix
+/// the byte order of a field at compile time. See [`PrimitiveRead::read_endian`] and
+/// [`Read::read_with_endian`].
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum Endian
+{
+  Little,
+  Big,
+}
+
 /// This trait can be used to read data types that can be represented in either big-endian or
 /// little-endian format like [`u64`].
 pub trait PrimitiveRead
@@ -118,6 +137,30 @@ pub trait PrimitiveRead
   {
     Self::read_be(reader)
   }
+
+  /// Reads a primitive type from a source using a byte order chosen at runtime.
+  /// ```
+  /// use std::io::Cursor;
+  /// use structurs::{Endian, PrimitiveRead};
+  ///
+  /// fn main()
+  /// {
+  ///   let mut c: Cursor<Vec<u8>> = Cursor::new(vec![87, 0, 0, 0]);
+  ///   let val = u32::read_endian(&mut c, Endian::Little).unwrap();
+  ///   assert_eq!(87, val);
+  /// }
+  /// ```
+  #[inline]
+  fn read_endian<R>(reader: &mut R, endian: Endian) -> io::Result<Self>
+  where
+    R: io::Read,
+    Self: Sized,
+  {
+    match endian {
+      Endian::Little => Self::read_le(reader),
+      Endian::Big => Self::read_be(reader),
+    }
+  }
 }
 
 /// This trait can be used to read data structures that are composed of other fields that implement
@@ -128,4 +171,30 @@ pub trait Read
   where
     R: io::Read,
     Self: Sized;
+
+  /// Reads a value using a byte order chosen at runtime, for fields marked `#[endian]` by
+  /// `#[derive(structurs::Read)]`. Types with no such fields can ignore `_endian` and just
+  /// delegate to [`Read::read`].
+  #[inline]
+  fn read_with_endian<R>(reader: &mut R, _endian: Endian) -> io::Result<Self>
+  where
+    R: io::Read,
+    Self: Sized,
+  {
+    Self::read(reader)
+  }
+
+  /// Reads a value from a source that also supports [`std::io::Seek`], for types with fields
+  /// marked `#[align]`, `#[seek_before]` or `#[pad_after]` by `#[derive(structurs::Read)]`. Those
+  /// fields reposition the stream directly instead of reading and discarding bytes, which is
+  /// cheaper for large inter-record gaps but requires a seekable source. Types with no such fields
+  /// can ignore the extra bound and just delegate to [`Read::read`].
+  #[inline]
+  fn read_seek<R>(reader: &mut R) -> io::Result<Self>
+  where
+    R: io::Read + io::Seek,
+    Self: Sized,
+  {
+    Self::read(reader)
+  }
 }
@@ -0,0 +1,56 @@
+/// A type whose serialized size in bytes is known without reading or writing a value, where
+/// possible. Implemented for the primitive types and for [`structurs::Pad`], and generated by
+/// `#[derive(structurs::StaticSize)]` for structs and tagged enums built out of them.
+///
+/// `SIZE` is `None` when the type contains a field whose length can't be determined ahead of time,
+/// such as a `#[count(<expr>)]` `Vec<T>` field.
+pub trait StaticSize
+{
+  const SIZE: Option<usize>;
+}
+
+/// Sums two field sizes, propagating `None` if either is unknown. Used by the code generated by
+/// `#[derive(structurs::StaticSize)]`.
+#[doc(hidden)]
+pub const fn static_size_add(a: Option<usize>, b: Option<usize>) -> Option<usize>
+{
+  match (a, b) {
+    (Some(a), Some(b)) => Some(a + b),
+    _ => None,
+  }
+}
+
+/// Multiplies a field size by an element count, propagating `None`. Used by the code generated by
+/// `#[derive(structurs::StaticSize)]`.
+#[doc(hidden)]
+pub const fn static_size_mul(size: Option<usize>, count: usize) -> Option<usize>
+{
+  match size {
+    Some(size) => Some(size * count),
+    None => None,
+  }
+}
+
+/// Resolves the `SIZE` of a tagged enum: `Some` only if every variant (tag included) agrees on the
+/// same size, `None` otherwise. Used by the code generated by `#[derive(structurs::StaticSize)]`.
+#[doc(hidden)]
+pub const fn static_size_agree(sizes: &[Option<usize>]) -> Option<usize>
+{
+  if sizes.is_empty() {
+    return None;
+  }
+  let first = sizes[0];
+  let mut i = 1;
+  while i < sizes.len() {
+    let matches = match (sizes[i], first) {
+      (Some(a), Some(b)) => a == b,
+      (None, None) => true,
+      _ => false,
+    };
+    if !matches {
+      return None;
+    }
+    i += 1;
+  }
+  first
+}
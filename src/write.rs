@@ -1,5 +1,7 @@
 use std::io;
 
+use crate::Endian;
+
 pub trait Writer: io::Write
 {
   #[inline]
@@ -28,6 +30,15 @@ pub trait Writer: io::Write
   {
     T::write_be(v, self)
   }
+
+  #[inline]
+  fn write_seek<T>(&mut self, v: &T) -> io::Result<()>
+  where
+    T: Write,
+    Self: Sized + io::Seek,
+  {
+    T::write_seek(v, self)
+  }
 }
 
 impl<T> Writer for T where T: io::Write {}
@@ -63,6 +74,30 @@ pub trait PrimitiveWrite
   {
     Self::write_be(self, writer)
   }
+
+  /// Writes a primitive type to a destination using a byte order chosen at runtime.
+  /// ```
+  /// use std::io::Cursor;
+  /// use structurs::{Endian, PrimitiveWrite};
+  ///
+  /// fn main()
+  /// {
+  ///   let mut out: Cursor<Vec<u8>> = Cursor::new(Vec::new());
+  ///   87u32.write_endian(&mut out, Endian::Little).unwrap();
+  ///   assert_eq!(vec![87, 0, 0, 0], out.into_inner());
+  /// }
+  /// ```
+  #[inline]
+  fn write_endian<W>(&self, writer: &mut W, endian: Endian) -> io::Result<()>
+  where
+    W: io::Write,
+    Self: Sized,
+  {
+    match endian {
+      Endian::Little => self.write_le(writer),
+      Endian::Big => self.write_be(writer),
+    }
+  }
 }
 
 pub trait Write
@@ -71,4 +106,29 @@ pub trait Write
   where
     W: io::Write,
     Self: Sized;
+
+  /// Writes a value using a byte order chosen at runtime, for fields marked `#[endian]` by
+  /// `#[derive(structurs::Write)]`. Types with no such fields can ignore `_endian` and just
+  /// delegate to [`Write::write`].
+  #[inline]
+  fn write_with_endian<W>(&self, writer: &mut W, _endian: Endian) -> io::Result<()>
+  where
+    W: io::Write,
+    Self: Sized,
+  {
+    self.write(writer)
+  }
+
+  /// Writes a value to a destination that also supports [`std::io::Seek`], for types with fields
+  /// marked `#[align]`, `#[seek_before]` or `#[pad_after]` by `#[derive(structurs::Write)]`. Those
+  /// fields reposition the stream directly instead of writing zero bytes. Types with no such
+  /// fields can ignore the extra bound and just delegate to [`Write::write`].
+  #[inline]
+  fn write_seek<W>(&self, writer: &mut W) -> io::Result<()>
+  where
+    W: io::Write + io::Seek,
+    Self: Sized,
+  {
+    Self::write(self, writer)
+  }
 }
@@ -67,6 +67,20 @@ struct Attributes
 {
   endian: Endian,
   padding: Option<Padding>,
+  count: Option<syn::Expr>,
+  /// Set by `#[endian]`: the byte order is chosen at runtime via `Read::read_with_endian` rather
+  /// than fixed by `endian`.
+  runtime_endian: bool,
+  /// Set by `#[read_if(<expr>)]`: the field is only read/written when the condition holds.
+  read_if: Option<syn::Expr>,
+  /// Set by `#[align = <expr>]`: seek forward to the next multiple of `<expr>` bytes.
+  align: Option<syn::Expr>,
+  /// Set by `#[seek_before = <expr>]`: seek to the absolute offset `<expr>` before the next field.
+  seek_before: Option<syn::Expr>,
+  /// Set by `#[pad_after = <expr>]`: seek forward `<expr>` bytes.
+  pad_after: Option<syn::Expr>,
+  /// Set by `#[bits = <expr>]`: the field occupies `<expr>` bits rather than whole bytes.
+  bits: Option<syn::Expr>,
 }
 
 impl Attributes
@@ -84,11 +98,115 @@ impl Attributes
           attributes.endian = Endian::Native
         } else if segment.ident == "pad" {
           attributes.padding = Some(Padding::parse(attr));
+        } else if segment.ident == "count" {
+          attributes.count = Some(parse_group_expr_attr(attr));
+        } else if segment.ident == "endian" {
+          attributes.runtime_endian = true;
+        } else if segment.ident == "read_if" {
+          attributes.read_if = Some(parse_group_expr_attr(attr));
+        } else if segment.ident == "align" {
+          attributes.align = Some(parse_expr_attr(attr));
+        } else if segment.ident == "seek_before" {
+          attributes.seek_before = Some(parse_expr_attr(attr));
+        } else if segment.ident == "pad_after" {
+          attributes.pad_after = Some(parse_expr_attr(attr));
+        } else if segment.ident == "bits" {
+          attributes.bits = Some(parse_expr_attr(attr));
         }
       }
     }
     attributes
   }
+
+  /// Whether this field carries a seek directive (`#[align]`, `#[seek_before]` or `#[pad_after]`).
+  fn has_seek_directive(&self) -> bool
+  {
+    self.align.is_some() || self.seek_before.is_some() || self.pad_after.is_some()
+  }
+}
+
+/// Whether a field's seek directives (`#[align]`/`#[seek_before]`/`#[pad_after]`) are honored, via
+/// a stream reposition, or rejected with an `io::Error` because the reader/writer isn't known to
+/// be seekable.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum SeekMode
+{
+  NonSeek,
+  Seek,
+}
+
+/// Whether a field's byte order is fixed at compile time or chosen at runtime (`#[endian]`).
+#[derive(Clone, Copy)]
+enum EndianMode
+{
+  Fixed,
+  Runtime,
+}
+
+/// Resolves how to read a value whose endianness may be fixed at compile time (`#[le]`/`#[be]`/
+/// `#[ne]`/none) or deferred, via `#[endian]`, to the runtime `endian` argument of
+/// `Read::read_with_endian`. In `EndianMode::Fixed` an `#[endian]` field falls back to native
+/// order, since no runtime argument is available from plain `Read::read`.
+fn endian_read_call(ty: &syn::Type, attrs: &Attributes, mode: EndianMode) -> proc_macro2::TokenStream
+{
+  if attrs.runtime_endian {
+    match mode {
+      EndianMode::Runtime => quote! { <#ty as ::structurs::PrimitiveRead>::read_endian(reader, endian)? },
+      EndianMode::Fixed => read_func(ty, &Endian::Native),
+    }
+  } else {
+    read_func(ty, &attrs.endian)
+  }
+}
+
+/// Resolves how to write a value whose endianness may be fixed at compile time (`#[le]`/`#[be]`/
+/// `#[ne]`/none) or deferred, via `#[endian]`, to the runtime `endian` argument of
+/// `Write::write_with_endian`. In `EndianMode::Fixed` an `#[endian]` field falls back to native
+/// order, since no runtime argument is available from plain `Write::write`.
+fn endian_write_call(
+  ty: &syn::Type,
+  attrs: &Attributes,
+  mode: EndianMode,
+  target: proc_macro2::TokenStream,
+) -> proc_macro2::TokenStream
+{
+  if attrs.runtime_endian {
+    match mode {
+      EndianMode::Runtime => quote! { <#ty as ::structurs::PrimitiveWrite>::write_endian(#target, writer, endian)?; },
+      EndianMode::Fixed => write_func(ty, &Endian::Native, target),
+    }
+  } else {
+    write_func(ty, &attrs.endian, target)
+  }
+}
+
+/// Parses the expression out of a field-level `#[align = <expr>]`, `#[seek_before = <expr>]`,
+/// `#[pad_after = <expr>]` or `#[bits = <expr>]` attribute.
+fn parse_expr_attr(attr: &syn::Attribute) -> syn::Expr
+{
+  let mut tokens = attr.tokens.clone().into_iter();
+  match tokens.next() {
+    Some(proc_macro2::TokenTree::Punct(ref p)) => assert_eq!(p.as_char(), '='),
+    token => panic!("expected punct was '=', but found: {:?}", token),
+  }
+  let rest: proc_macro2::TokenStream = tokens.collect();
+  syn::parse2(rest).unwrap_or_else(|err| {
+    panic!("a parsing error occurred while reading the expression inside an attribute: {}", err);
+  })
+}
+
+/// Parses the expression out of a field-level `#[count(<expr>)]` or `#[read_if(<expr>)]`
+/// attribute. Unlike `#[attr = <expr>]`, which rustc only accepts when `<expr>` is a bare literal,
+/// the delimited-group form is handed to the macro as a raw token stream and can hold an arbitrary
+/// expression, such as a reference to an earlier field.
+fn parse_group_expr_attr(attr: &syn::Attribute) -> syn::Expr
+{
+  match attr.tokens.clone().into_iter().next() {
+    Some(proc_macro2::TokenTree::Group(g)) => syn::parse2(g.stream()).unwrap_or_else(|err| {
+      panic!("a parsing error occurred while reading the expression inside an attribute: {}", err);
+    }),
+    token => panic!("expected a delimited group, but found: {:?}", token),
+  }
 }
 
 enum ArrayLength
@@ -97,90 +215,767 @@ enum ArrayLength
   Const(syn::Expr),
 }
 
-#[proc_macro_derive(Read, attributes(le, be, ne, pad))]
-pub fn derive_read_struct(input: TokenStream) -> TokenStream
+/// Reads the tag type out of an enum-level `#[tag(..)]` attribute, e.g. `u16` in `#[tag(u16)]`.
+fn parse_tag_type(attrs: &Vec<syn::Attribute>) -> Option<syn::Type>
 {
-  let ast = parse_macro_input!(input as DeriveInput);
-  let struct_name = &ast.ident;
-  let generics = &ast.generics;
-  let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+  for attr in attrs {
+    for segment in &attr.path.segments {
+      if segment.ident == "tag" {
+        if let Some(proc_macro2::TokenTree::Group(g)) = attr.tokens.clone().into_iter().next() {
+          return Some(syn::parse2(g.stream()).unwrap_or_else(|err| {
+            panic!("a parsing error occurred while reading the type inside '#[tag(..)]': {}", err);
+          }));
+        }
+      }
+    }
+  }
+  None
+}
 
-  // fields of the input struct must be named (at least for now).
-  let fields = if let syn::Data::Struct(syn::DataStruct {
-    fields: syn::Fields::Named(syn::FieldsNamed { ref named, .. }),
-    ..
-  }) = ast.data
-  {
-    named
+/// Reads the constant tag value out of a variant-level `#[tag_value = ..]` attribute.
+fn parse_tag_value(attrs: &Vec<syn::Attribute>) -> Option<syn::Lit>
+{
+  for attr in attrs {
+    for segment in &attr.path.segments {
+      if segment.ident == "tag_value" {
+        let mut tokens = attr.tokens.clone().into_iter();
+        match tokens.next() {
+          Some(proc_macro2::TokenTree::Punct(ref p)) => assert_eq!(p.as_char(), '='),
+          token => panic!("expected punct was '=', but found: {:?}", token),
+        }
+        return match tokens.next() {
+          Some(proc_macro2::TokenTree::Literal(l)) => Some(syn::Lit::new(l)),
+          token => panic!("expected a literal, but found: {:?}", token),
+        };
+      }
+    }
+  }
+  None
+}
+
+/// `elem_ty` is the type of the element if `ty` is an array, otherwise it is `ty` itself.
+/// `elements` is the number of elements the array has and if it is not an array, then it is
+/// simply 1.
+fn field_shape(ty: &syn::Type) -> (&syn::Type, ArrayLength)
+{
+  match array_type(ty) {
+    Some(elems) => elems,
+    None => (ty, ArrayLength::Int(1)),
+  }
+}
+
+/// If `ty` is `Vec<T>`, returns `T`.
+fn vec_elem_type(ty: &syn::Type) -> Option<&syn::Type>
+{
+  let path = if let syn::Type::Path(syn::TypePath { path, .. }) = ty {
+    path
+  } else {
+    return None;
+  };
+  let segment = path.segments.last()?;
+  if segment.ident != "Vec" {
+    return None;
+  }
+  if let syn::PathArguments::AngleBracketed(syn::AngleBracketedGenericArguments { args, .. }) = &segment.arguments {
+    if let Some(syn::GenericArgument::Type(elem_ty)) = args.first() {
+      return Some(elem_ty);
+    }
+  }
+  None
+}
+
+/// If `ty` is `Option<T>`, returns `T`.
+fn option_elem_type(ty: &syn::Type) -> Option<&syn::Type>
+{
+  let path = if let syn::Type::Path(syn::TypePath { path, .. }) = ty {
+    path
   } else {
-    panic!("'Read' derive macro only supports structs with named fields.");
+    return None;
   };
+  let segment = path.segments.last()?;
+  if segment.ident != "Option" {
+    return None;
+  }
+  if let syn::PathArguments::AngleBracketed(syn::AngleBracketedGenericArguments { args, .. }) = &segment.arguments {
+    if let Some(syn::GenericArgument::Type(elem_ty)) = args.first() {
+      return Some(elem_ty);
+    }
+  }
+  None
+}
+
+/// Whether any field in a block of named fields is marked `#[endian]`.
+fn has_runtime_endian_field(fields: &syn::punctuated::Punctuated<syn::Field, syn::token::Comma>) -> bool
+{
+  fields.iter().any(|f| Attributes::new(&f.attrs).runtime_endian)
+}
+
+/// Generates a `let field_name = <value>;` binding for each named field, in declaration order,
+/// plus the list of field names to use in the resulting struct/variant literal. Binding each
+/// field to a local lets a later field's `#[count(<expr>)]` refer to an earlier one.
+fn read_named_fields(
+  fields: &syn::punctuated::Punctuated<syn::Field, syn::token::Comma>,
+  mode: EndianMode,
+  seek_mode: SeekMode,
+) -> (Vec<proc_macro2::TokenStream>, Vec<&Option<syn::Ident>>)
+{
+  let mut bindings = Vec::new();
+  let mut field_names = Vec::new();
+
+  let items: Vec<&syn::Field> = fields.iter().collect();
+  let mut i = 0;
+  while i < items.len() {
+    let f = items[i];
+
+    // A run of consecutive `#[bits = ..]` fields shares a single `BitReader` so leftover bits from
+    // a partial byte carry over between them; the run ends (aligning to the next byte boundary) at
+    // the first field that isn't a bit-field, or the end of the struct.
+    if Attributes::new(&f.attrs).bits.is_some() {
+      let mut run = Vec::new();
+      while i < items.len() {
+        let attrs = Attributes::new(&items[i].attrs);
+        if let Some(bits_expr) = attrs.bits {
+          run.push((items[i], bits_expr));
+          i += 1;
+        } else {
+          break;
+        }
+      }
+
+      let run_field_names: Vec<&Option<syn::Ident>> = run.iter().map(|(f, _)| &f.ident).collect();
+      let run_reads = run.iter().map(|(f, bits_expr)| {
+        let field_name = &f.ident;
+        let elem_ty = &f.ty;
+        quote! {
+          let #field_name = {
+            let bits: u32 = (#bits_expr) as u32;
+            // BitReader accumulates into a u64, so the widest field this can ever service is 64
+            // bits, even if the target type itself is wider (e.g. u128).
+            let max_bits = (::std::mem::size_of::<#elem_ty>() * 8).min(64) as u32;
+            if bits > max_bits {
+              return Err(::std::io::Error::new(
+                ::std::io::ErrorKind::InvalidData,
+                format!("'#[bits = {}]' exceeds the bit width of '{}'", bits, stringify!(#elem_ty)),
+              ));
+            }
+            __bits.read_bits(bits)? as #elem_ty
+          };
+        }
+      });
+      bindings.push(quote! {
+        let ( #(#run_field_names),* ) = {
+          let mut __bits = ::structurs::BitReader::new(&mut *reader);
+          #(#run_reads)*
+          ( #(#run_field_names),* )
+        };
+      });
+      field_names.extend(run_field_names);
+      continue;
+    }
+
+    i += 1;
 
-  // Fields to pass into struct construction block.
-  let read_impl_fields = fields.iter().map(|f| {
     let field_name = &f.ident;
-    // `elem_ty` is the type of the element if the field type is an array, otherwise it is the type
-    // of the field. `elements` is the number of elements the array has and if it is not an array,
-    // then it is simply 1;
-    let (elem_ty, elements) = match array_type(&f.ty) {
-      Some(elems) => elems,
-      None => (&f.ty, ArrayLength::Int(1)),
-    };
 
     // Read attributes passed to this field.
     let attrs = Attributes::new(&f.attrs);
 
-    let read_func_token = read_func(elem_ty, &attrs.endian);
-    let read_func_body = get_body(&read_func_token, elem_ty, &elements);
+    let body = if seek_mode == SeekMode::Seek && attrs.has_seek_directive() {
+      if let Some(align) = &attrs.align {
+        quote! { {
+          let align: u64 = (#align) as u64;
+          let pos = reader.stream_position()?;
+          let rem = pos % align;
+          if rem != 0 {
+            reader.seek(::std::io::SeekFrom::Current((align - rem) as i64))?;
+          }
+          ::std::default::Default::default() }
+        }
+      } else if let Some(offset) = &attrs.seek_before {
+        quote! { {
+          reader.seek(::std::io::SeekFrom::Start((#offset) as u64))?;
+          ::std::default::Default::default() }
+        }
+      } else {
+        let bytes = attrs.pad_after.as_ref().unwrap();
+        quote! { {
+          reader.seek(::std::io::SeekFrom::Current((#bytes) as i64))?;
+          ::std::default::Default::default() }
+        }
+      }
+    } else if attrs.has_seek_directive() {
+      // No `Seek` bound is available here (plain `Read::read`). The byte span a seek directive
+      // covers generally can't be replayed without knowing the stream position, so fail loudly
+      // instead of silently desyncing the stream by skipping the I/O entirely.
+      quote! {
+        return Err(::std::io::Error::new(
+          ::std::io::ErrorKind::InvalidData,
+          format!(
+            "field '{}' has '#[align]'/'#[seek_before]'/'#[pad_after]'; use read_seek instead of read",
+            stringify!(#field_name),
+          ),
+        ))
+      }
+    } else if let Some(cond_expr) = &attrs.read_if {
+      let elem_ty = option_elem_type(&f.ty).unwrap_or_else(|| {
+        panic!("'#[read_if(..)]' can only be used on a field of type 'Option<T>'");
+      });
+      let read_elem = endian_read_call(elem_ty, &attrs, mode);
+      quote! {
+        if #cond_expr {
+          Some(#read_elem)
+        } else {
+          None
+        }
+      }
+    } else if let Some(count_expr) = &attrs.count {
+      let elem_ty = vec_elem_type(&f.ty).unwrap_or_else(|| {
+        panic!("'#[count(..)]' can only be used on a field of type 'Vec<T>'");
+      });
+      let read_elem = endian_read_call(elem_ty, &attrs, mode);
+      quote! { {
+        let count: usize = (#count_expr) as usize;
+        let mut elements: ::std::vec::Vec<#elem_ty> = ::std::vec::Vec::with_capacity(count);
+        for _ in 0..count {
+          elements.push(#read_elem);
+        }
+        elements }
+      }
+    } else {
+      let (elem_ty, elements) = field_shape(&f.ty);
 
-    let default_func_token = quote! { <#elem_ty as ::std::default::Default>::default() };
-    let default_func_body = get_body(&default_func_token, elem_ty, &elements);
+      let read_func_token = endian_read_call(elem_ty, &attrs, mode);
+      let read_func_body = get_body(&read_func_token, elem_ty, &elements);
 
-    let body = if let Some(pad) = attrs.padding {
-      match pad {
-        Padding::Read => {
-          let elements_token = match &elements {
-            ArrayLength::Int(size) => quote! { #size },
-            ArrayLength::Const(expr) => quote! { #expr },
-          };
-          quote! { {
-            const PAD_SIZE: usize = ::std::mem::size_of::<#elem_ty>() * #elements_token;
-            let mut pad_buf: [u8; PAD_SIZE] = [0; PAD_SIZE];
-            reader.read_exact(&mut pad_buf[..])?;
-            #default_func_body }
+      let default_func_token = quote! { <#elem_ty as ::std::default::Default>::default() };
+      let default_func_body = get_body(&default_func_token, elem_ty, &elements);
+
+      if let Some(pad) = attrs.padding {
+        match pad {
+          Padding::Read => {
+            let elements_token = match &elements {
+              ArrayLength::Int(size) => quote! { #size },
+              ArrayLength::Const(expr) => quote! { #expr },
+            };
+            quote! { {
+              const PAD_SIZE: usize = ::std::mem::size_of::<#elem_ty>() * #elements_token;
+              let mut pad_buf: [u8; PAD_SIZE] = [0; PAD_SIZE];
+              reader.read_exact(&mut pad_buf[..])?;
+              #default_func_body }
+            }
+          }
+          Padding::Bytes(bytes) => {
+            quote! { {
+              let mut pad_buf: [u8; #bytes] = [0; #bytes];
+              reader.read_exact(&mut pad_buf)?;
+              #default_func_body }
+            }
+          }
+        }
+      } else {
+        quote! { #read_func_body }
+      }
+    };
+
+    bindings.push(quote! { let #field_name = #body; });
+    field_names.push(field_name);
+  }
+
+  (bindings, field_names)
+}
+
+/// Generates statements that write a block of named fields to the writer, in declaration order.
+/// `base` builds the place expression a given field is accessed through: `self.field` for a
+/// struct, or the match-bound identifier for an enum variant.
+fn write_named_fields(
+  fields: &syn::punctuated::Punctuated<syn::Field, syn::token::Comma>,
+  base: impl Fn(&Option<syn::Ident>) -> proc_macro2::TokenStream,
+  mode: EndianMode,
+  seek_mode: SeekMode,
+) -> Vec<proc_macro2::TokenStream>
+{
+  let mut statements = Vec::new();
+
+  let items: Vec<&syn::Field> = fields.iter().collect();
+  let mut i = 0;
+  while i < items.len() {
+    let f = items[i];
+
+    // A run of consecutive `#[bits = ..]` fields shares a single `BitWriter` so they pack into the
+    // same underlying bytes; the run is flushed, zero-padded to a byte boundary, at the first field
+    // that isn't a bit-field, or the end of the struct.
+    if Attributes::new(&f.attrs).bits.is_some() {
+      let mut run = Vec::new();
+      while i < items.len() {
+        let attrs = Attributes::new(&items[i].attrs);
+        if let Some(bits_expr) = attrs.bits {
+          run.push((items[i], bits_expr));
+          i += 1;
+        } else {
+          break;
+        }
+      }
+
+      let run_writes = run.iter().map(|(f, bits_expr)| {
+        let field_name = &f.ident;
+        let elem_ty = &f.ty;
+        let place = base(field_name);
+        quote! {
+          let bits: u32 = (#bits_expr) as u32;
+          // BitWriter accumulates into a u64, so the widest field this can ever service is 64
+          // bits, even if the target type itself is wider (e.g. u128).
+          let max_bits = (::std::mem::size_of::<#elem_ty>() * 8).min(64) as u32;
+          if bits > max_bits {
+            return Err(::std::io::Error::new(
+              ::std::io::ErrorKind::InvalidData,
+              format!("'#[bits = {}]' exceeds the bit width of '{}'", bits, stringify!(#elem_ty)),
+            ));
           }
+          __bits.write_bits(#place as u64, bits)?;
+        }
+      });
+      statements.push(quote! {
+        {
+          let mut __bits = ::structurs::BitWriter::new(&mut *writer);
+          #(#run_writes)*
+          __bits.finish()?;
         }
-        Padding::Bytes(bytes) => {
-          quote! { {
-            let mut pad_buf: [u8; #bytes] = [0; #bytes];
-            reader.read_exact(&mut pad_buf)?;
-            #default_func_body }
+      });
+      continue;
+    }
+
+    i += 1;
+
+    let field_name = &f.ident;
+
+    // Read attributes passed to this field.
+    let attrs = Attributes::new(&f.attrs);
+
+    let statement = if seek_mode == SeekMode::Seek && attrs.has_seek_directive() {
+      if let Some(align) = &attrs.align {
+        quote! {
+          let align: u64 = (#align) as u64;
+          let pos = writer.stream_position()?;
+          let rem = pos % align;
+          if rem != 0 {
+            writer.seek(::std::io::SeekFrom::Current((align - rem) as i64))?;
           }
         }
+      } else if let Some(offset) = &attrs.seek_before {
+        quote! {
+          writer.seek(::std::io::SeekFrom::Start((#offset) as u64))?;
+        }
+      } else {
+        let bytes = attrs.pad_after.as_ref().unwrap();
+        quote! {
+          writer.seek(::std::io::SeekFrom::Current((#bytes) as i64))?;
+        }
+      }
+    } else if attrs.has_seek_directive() {
+      // No `Seek` bound is available here (plain `Write::write`). The byte span a seek directive
+      // covers generally can't be replayed without knowing the stream position, so fail loudly
+      // instead of silently desyncing the stream by skipping the I/O entirely.
+      quote! {
+        return Err(::std::io::Error::new(
+          ::std::io::ErrorKind::InvalidData,
+          format!(
+            "field '{}' has '#[align]'/'#[seek_before]'/'#[pad_after]'; use write_seek instead of write",
+            stringify!(#field_name),
+          ),
+        ));
+      }
+    } else if attrs.read_if.is_some() {
+      let elem_ty = option_elem_type(&f.ty).unwrap_or_else(|| {
+        panic!("'#[read_if(..)]' can only be used on a field of type 'Option<T>'");
+      });
+      let write_elem = endian_write_call(elem_ty, &attrs, mode, quote! { value });
+      let place = base(field_name);
+      quote! {
+        if let Some(value) = &#place {
+          #write_elem
+        }
+      }
+    } else if attrs.count.is_some() {
+      let elem_ty = vec_elem_type(&f.ty).unwrap_or_else(|| {
+        panic!("'#[count(..)]' can only be used on a field of type 'Vec<T>'");
+      });
+      let write_elem = endian_write_call(elem_ty, &attrs, mode, quote! { elem });
+      let place = base(field_name);
+      quote! {
+        for elem in #place.iter() {
+          #write_elem
+        }
       }
     } else {
-      quote! { #read_func_body }
+      let (elem_ty, elements) = field_shape(&f.ty);
+
+      if let Some(pad) = attrs.padding {
+        let elements_token = match &elements {
+          ArrayLength::Int(size) => quote! { #size },
+          ArrayLength::Const(expr) => quote! { #expr },
+        };
+        match pad {
+          Padding::Read => quote! { {
+            const PAD_SIZE: usize = ::std::mem::size_of::<#elem_ty>() * (#elements_token);
+            writer.write_all(&[0u8; PAD_SIZE])?;
+          } },
+          Padding::Bytes(bytes) => quote! {
+            writer.write_all(&[0u8; #bytes])?;
+          },
+        }
+      } else {
+        write_func_body(base(field_name), elem_ty, &attrs, mode, &elements)
+      }
     };
 
-    quote! { #field_name: #body }
-  });
+    statements.push(statement);
+  }
+
+  statements
+}
+
+#[proc_macro_derive(
+  Read,
+  attributes(le, be, ne, pad, tag, tag_value, count, endian, read_if, align, seek_before, pad_after, bits)
+)]
+pub fn derive_read_struct(input: TokenStream) -> TokenStream
+{
+  let ast = parse_macro_input!(input as DeriveInput);
+  let name = &ast.ident;
+  let generics = &ast.generics;
+  let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+  let body = read_body(&ast, EndianMode::Fixed, SeekMode::NonSeek);
+
+  // Only generate `read_with_endian` when a field actually defers its byte order to it; otherwise
+  // the default implementation on `Read` (which just forwards to `read`) is correct as-is.
+  let read_with_endian = if needs_runtime_endian(&ast) {
+    let body = read_body(&ast, EndianMode::Runtime, SeekMode::NonSeek);
+    quote! {
+      fn read_with_endian<R>(reader: &mut R, endian: ::structurs::Endian) -> ::std::io::Result<Self>
+      where
+        R: ::std::io::Read
+      {
+        #body
+      }
+    }
+  } else {
+    quote! {}
+  };
+
+  // Only generate `read_seek` when a field actually carries a seek directive; otherwise the
+  // default implementation on `Read` (which just forwards to `read`) is correct as-is.
+  let read_seek = if needs_seek(&ast) {
+    let body = read_body(&ast, EndianMode::Fixed, SeekMode::Seek);
+    quote! {
+      fn read_seek<R>(reader: &mut R) -> ::std::io::Result<Self>
+      where
+        R: ::std::io::Read + ::std::io::Seek
+      {
+        #body
+      }
+    }
+  } else {
+    quote! {}
+  };
 
   let expanded = quote! {
-    impl #impl_generics ::structurs::Read for #struct_name #ty_generics #where_clause {
+    impl #impl_generics ::structurs::Read for #name #ty_generics #where_clause {
       fn read<R>(reader: &mut R) -> ::std::io::Result<Self>
       where
         R: ::std::io::Read
       {
+        #body
+      }
+
+      #read_with_endian
+
+      #read_seek
+    }
+  };
+
+  expanded.into()
+}
+
+/// Whether `ast` declares any field (directly, or in any variant of a tagged enum) marked
+/// `#[align]`, `#[seek_before]` or `#[pad_after]`.
+fn needs_seek(ast: &DeriveInput) -> bool
+{
+  fn has_seek_field(fields: &syn::punctuated::Punctuated<syn::Field, syn::token::Comma>) -> bool
+  {
+    fields.iter().any(|f| Attributes::new(&f.attrs).has_seek_directive())
+  }
+
+  match &ast.data {
+    syn::Data::Struct(syn::DataStruct {
+      fields: syn::Fields::Named(syn::FieldsNamed { named, .. }),
+      ..
+    }) => has_seek_field(named),
+    syn::Data::Enum(syn::DataEnum { variants, .. }) => variants.iter().any(|variant| match &variant.fields {
+      syn::Fields::Named(syn::FieldsNamed { named, .. }) => has_seek_field(named),
+      _ => false,
+    }),
+    _ => false,
+  }
+}
+
+/// Whether `ast` declares any field (directly, or in any variant of a tagged enum) marked
+/// `#[endian]`, or marks its own tag `#[endian]`.
+fn needs_runtime_endian(ast: &DeriveInput) -> bool
+{
+  match &ast.data {
+    syn::Data::Struct(syn::DataStruct {
+      fields: syn::Fields::Named(syn::FieldsNamed { named, .. }),
+      ..
+    }) => has_runtime_endian_field(named),
+    syn::Data::Enum(syn::DataEnum { variants, .. }) => {
+      Attributes::new(&ast.attrs).runtime_endian
+        || variants.iter().any(|variant| match &variant.fields {
+          syn::Fields::Named(syn::FieldsNamed { named, .. }) => has_runtime_endian_field(named),
+          _ => false,
+        })
+    }
+    _ => false,
+  }
+}
+
+/// Generates the body of `Read::read`/`Read::read_with_endian`/`Read::read_seek` for the struct or
+/// tagged enum `ast` derives from.
+fn read_body(ast: &DeriveInput, mode: EndianMode, seek_mode: SeekMode) -> proc_macro2::TokenStream
+{
+  match &ast.data {
+    syn::Data::Struct(syn::DataStruct {
+      fields: syn::Fields::Named(syn::FieldsNamed { named, .. }),
+      ..
+    }) => {
+      let (read_impl_fields, field_names) = read_named_fields(named, mode, seek_mode);
+      quote! {
+        #(#read_impl_fields)*
         Ok(Self {
-          #(#read_impl_fields,)*
+          #(#field_names,)*
         })
       }
     }
+    syn::Data::Enum(syn::DataEnum { variants, .. }) => {
+      read_tagged_enum(&ast.attrs, &ast.ident, variants, mode, seek_mode)
+    }
+    _ => panic!("'Read' derive macro only supports structs with named fields and tagged enums."),
+  }
+}
+
+/// Generates the body of `Read::read`/`Read::read_with_endian`/`Read::read_seek` for an enum whose
+/// variant is selected by a leading tag value, e.g. `#[tag(u16)] enum Frame { #[tag_value = 1]
+/// Ping { .. } }`.
+fn read_tagged_enum(
+  attrs: &Vec<syn::Attribute>,
+  enum_name: &syn::Ident,
+  variants: &syn::punctuated::Punctuated<syn::Variant, syn::token::Comma>,
+  mode: EndianMode,
+  seek_mode: SeekMode,
+) -> proc_macro2::TokenStream
+{
+  let tag_ty = parse_tag_type(attrs)
+    .unwrap_or_else(|| panic!("enums must declare a tag type with '#[tag(..)]' to derive 'Read'"));
+  let enum_attrs = Attributes::new(attrs);
+  let read_tag = endian_read_call(&tag_ty, &enum_attrs, mode);
+
+  let arms = variants.iter().map(|variant| {
+    let variant_name = &variant.ident;
+    let tag_value = parse_tag_value(&variant.attrs).unwrap_or_else(|| {
+      panic!("variant '{}' is missing a '#[tag_value = ..]' attribute", variant_name)
+    });
+    let fields = if let syn::Fields::Named(syn::FieldsNamed { named, .. }) = &variant.fields {
+      named
+    } else {
+      panic!("'Read' derive macro only supports enum variants with named fields.");
+    };
+    let (read_impl_fields, field_names) = read_named_fields(fields, mode, seek_mode);
+    quote! {
+      #tag_value => {
+        #(#read_impl_fields)*
+        Self::#variant_name {
+          #(#field_names,)*
+        }
+      },
+    }
+  });
+
+  quote! {
+    let tag: #tag_ty = #read_tag;
+    Ok(match tag {
+      #(#arms)*
+      _ => return Err(::std::io::Error::new(
+        ::std::io::ErrorKind::InvalidData,
+        format!("unexpected tag value while reading '{}'", stringify!(#enum_name)),
+      )),
+    })
+  }
+}
+
+#[proc_macro_derive(
+  Write,
+  attributes(le, be, ne, pad, tag, tag_value, count, endian, read_if, align, seek_before, pad_after, bits)
+)]
+pub fn derive_write_struct(input: TokenStream) -> TokenStream
+{
+  let ast = parse_macro_input!(input as DeriveInput);
+  let name = &ast.ident;
+  let generics = &ast.generics;
+  let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+  let body = write_body(&ast, EndianMode::Fixed, SeekMode::NonSeek);
+
+  // Only generate `write_with_endian` when a field actually defers its byte order to it;
+  // otherwise the default implementation on `Write` (which just forwards to `write`) is correct
+  // as-is.
+  let write_with_endian = if needs_runtime_endian(&ast) {
+    let body = write_body(&ast, EndianMode::Runtime, SeekMode::NonSeek);
+    quote! {
+      fn write_with_endian<W>(&self, writer: &mut W, endian: ::structurs::Endian) -> ::std::io::Result<()>
+      where
+        W: ::std::io::Write
+      {
+        #body
+      }
+    }
+  } else {
+    quote! {}
+  };
+
+  // Only generate `write_seek` when a field actually carries a seek directive; otherwise the
+  // default implementation on `Write` (which just forwards to `write`) is correct as-is.
+  let write_seek = if needs_seek(&ast) {
+    let body = write_body(&ast, EndianMode::Fixed, SeekMode::Seek);
+    quote! {
+      fn write_seek<W>(&self, writer: &mut W) -> ::std::io::Result<()>
+      where
+        W: ::std::io::Write + ::std::io::Seek
+      {
+        #body
+      }
+    }
+  } else {
+    quote! {}
+  };
+
+  let expanded = quote! {
+    impl #impl_generics ::structurs::Write for #name #ty_generics #where_clause {
+      fn write<W>(&self, writer: &mut W) -> ::std::io::Result<()>
+      where
+        W: ::std::io::Write
+      {
+        #body
+      }
+
+      #write_with_endian
+
+      #write_seek
+    }
   };
 
   expanded.into()
 }
 
+/// Generates the body of `Write::write`/`Write::write_seek` for the struct or tagged enum `ast`
+/// derives from.
+fn write_body(ast: &DeriveInput, mode: EndianMode, seek_mode: SeekMode) -> proc_macro2::TokenStream
+{
+  match &ast.data {
+    syn::Data::Struct(syn::DataStruct {
+      fields: syn::Fields::Named(syn::FieldsNamed { named, .. }),
+      ..
+    }) => {
+      let write_impl_fields =
+        write_named_fields(named, |field_name| quote! { self.#field_name }, mode, seek_mode);
+      quote! {
+        #(#write_impl_fields)*
+        Ok(())
+      }
+    }
+    syn::Data::Enum(syn::DataEnum { variants, .. }) => write_tagged_enum(&ast.attrs, variants, mode, seek_mode),
+    _ => panic!("'Write' derive macro only supports structs with named fields and tagged enums."),
+  }
+}
+
+/// Generates the body of `Write::write`/`Write::write_with_endian`/`Write::write_seek` for an enum
+/// whose variant is selected by a leading tag value: the variant's `#[tag_value = ..]` is written
+/// first, then its fields.
+fn write_tagged_enum(
+  attrs: &Vec<syn::Attribute>,
+  variants: &syn::punctuated::Punctuated<syn::Variant, syn::token::Comma>,
+  mode: EndianMode,
+  seek_mode: SeekMode,
+) -> proc_macro2::TokenStream
+{
+  let tag_ty = parse_tag_type(attrs)
+    .unwrap_or_else(|| panic!("enums must declare a tag type with '#[tag(..)]' to derive 'Write'"));
+  let enum_attrs = Attributes::new(attrs);
+  let write_tag = endian_write_call(&tag_ty, &enum_attrs, mode, quote! { &tag_value });
+
+  let arms = variants.iter().map(|variant| {
+    let variant_name = &variant.ident;
+    let tag_value = parse_tag_value(&variant.attrs).unwrap_or_else(|| {
+      panic!("variant '{}' is missing a '#[tag_value = ..]' attribute", variant_name)
+    });
+    let fields = if let syn::Fields::Named(syn::FieldsNamed { named, .. }) = &variant.fields {
+      named
+    } else {
+      panic!("'Write' derive macro only supports enum variants with named fields.");
+    };
+    let field_names = fields.iter().map(|f| &f.ident);
+    let write_impl_fields = write_named_fields(fields, |field_name| quote! { #field_name }, mode, seek_mode);
+    quote! {
+      Self::#variant_name { #(#field_names,)* } => {
+        let tag_value: #tag_ty = #tag_value;
+        #write_tag
+        #(#write_impl_fields)*
+      }
+    }
+  });
+
+  quote! {
+    match self {
+      #(#arms)*
+    }
+    Ok(())
+  }
+}
+
+fn write_func(ty: &syn::Type, endian: &Endian, target: proc_macro2::TokenStream) -> proc_macro2::TokenStream
+{
+  match endian {
+    Endian::Little => quote! { <#ty as ::structurs::PrimitiveWrite>::write_le(#target, writer)?; },
+    Endian::Big => quote! { <#ty as ::structurs::PrimitiveWrite>::write_be(#target, writer)?; },
+    Endian::Native => quote! { <#ty as ::structurs::PrimitiveWrite>::write_ne(#target, writer)?; },
+    Endian::Normal => quote! { <#ty as ::structurs::Write>::write(#target, writer)?; },
+  }
+}
+
+fn write_func_body(
+  base: proc_macro2::TokenStream,
+  elem_ty: &syn::Type,
+  attrs: &Attributes,
+  mode: EndianMode,
+  ty_length: &ArrayLength,
+) -> proc_macro2::TokenStream
+{
+  match ty_length {
+    ArrayLength::Int(size) if *size == 1 => endian_write_call(elem_ty, attrs, mode, quote! { &#base }),
+    ArrayLength::Int(size) => {
+      let stmts = (0..*size).map(|i| endian_write_call(elem_ty, attrs, mode, quote! { &#base[#i] }));
+      quote! { #(#stmts)* }
+    }
+    ArrayLength::Const(expr) => {
+      let stmt = endian_write_call(elem_ty, attrs, mode, quote! { &#base[i] });
+      quote! {
+        for i in 0..#expr {
+          #stmt
+        }
+      }
+    }
+  }
+}
+
 fn read_func(ty: &syn::Type, endian: &Endian) -> proc_macro2::TokenStream
 {
   match endian {
@@ -232,3 +1027,122 @@ fn get_body(token: &proc_macro2::TokenStream, elem_ty: &syn::Type, ty_length: &A
     },
   }
 }
+
+#[proc_macro_derive(
+  StaticSize,
+  attributes(le, be, ne, pad, tag, tag_value, count, endian, read_if, align, seek_before, pad_after, bits)
+)]
+pub fn derive_static_size(input: TokenStream) -> TokenStream
+{
+  let ast = parse_macro_input!(input as DeriveInput);
+  let name = &ast.ident;
+  let generics = &ast.generics;
+  let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+  let size = static_size_body(&ast);
+
+  let expanded = quote! {
+    impl #impl_generics ::structurs::StaticSize for #name #ty_generics #where_clause {
+      const SIZE: ::std::option::Option<usize> = #size;
+    }
+  };
+
+  expanded.into()
+}
+
+/// Generates the `StaticSize::SIZE` expression for the struct or tagged enum `ast` derives from.
+fn static_size_body(ast: &DeriveInput) -> proc_macro2::TokenStream
+{
+  match &ast.data {
+    syn::Data::Struct(syn::DataStruct {
+      fields: syn::Fields::Named(syn::FieldsNamed { named, .. }),
+      ..
+    }) => static_size_named_fields(named),
+    syn::Data::Enum(syn::DataEnum { variants, .. }) => static_size_tagged_enum(&ast.attrs, variants),
+    _ => panic!("'StaticSize' derive macro only supports structs with named fields and tagged enums."),
+  }
+}
+
+/// Sums the `StaticSize::SIZE` contribution of each named field into a single `Option<usize>`
+/// const-expression, starting from `Some(0)`.
+fn static_size_named_fields(fields: &syn::punctuated::Punctuated<syn::Field, syn::token::Comma>) -> proc_macro2::TokenStream
+{
+  let mut size = quote! { ::std::option::Option::Some(0usize) };
+  for f in fields {
+    let contribution = static_size_field(f);
+    size = quote! { ::structurs::static_size_add(#size, #contribution) };
+  }
+  size
+}
+
+/// The `Option<usize>` contribution of a single named field to its struct's `StaticSize::SIZE`.
+fn static_size_field(f: &syn::Field) -> proc_macro2::TokenStream
+{
+  let attrs = Attributes::new(&f.attrs);
+
+  // A field whose length depends on something other than its type (a runtime count, a condition,
+  // a stream position, or bit-packing, which this derive doesn't attempt to size) makes the whole
+  // struct's size unknown.
+  if attrs.count.is_some()
+    || attrs.read_if.is_some()
+    || attrs.align.is_some()
+    || attrs.seek_before.is_some()
+    || attrs.bits.is_some()
+  {
+    return quote! { ::std::option::Option::None };
+  }
+
+  if let Some(pad) = attrs.padding {
+    return match pad {
+      Padding::Bytes(bytes) => quote! { ::std::option::Option::Some(#bytes) },
+      Padding::Read => {
+        let (elem_ty, elements) = field_shape(&f.ty);
+        let len = array_len_expr(&elements);
+        quote! { ::structurs::static_size_mul(<#elem_ty as ::structurs::StaticSize>::SIZE, #len) }
+      }
+    };
+  }
+
+  if let Some(bytes) = &attrs.pad_after {
+    return quote! { ::std::option::Option::Some((#bytes) as usize) };
+  }
+
+  let (elem_ty, elements) = field_shape(&f.ty);
+  let len = array_len_expr(&elements);
+  quote! { ::structurs::static_size_mul(<#elem_ty as ::structurs::StaticSize>::SIZE, #len) }
+}
+
+fn array_len_expr(elements: &ArrayLength) -> proc_macro2::TokenStream
+{
+  match elements {
+    ArrayLength::Int(size) => quote! { #size },
+    ArrayLength::Const(expr) => quote! { (#expr) },
+  }
+}
+
+/// Generates the `StaticSize::SIZE` expression for an enum whose variant is selected by a leading
+/// tag value: `Some` only if the tag plus every variant's fields agree on the same total size,
+/// `None` otherwise.
+fn static_size_tagged_enum(
+  attrs: &Vec<syn::Attribute>,
+  variants: &syn::punctuated::Punctuated<syn::Variant, syn::token::Comma>,
+) -> proc_macro2::TokenStream
+{
+  let tag_ty = parse_tag_type(attrs)
+    .unwrap_or_else(|| panic!("enums must declare a tag type with '#[tag(..)]' to derive 'StaticSize'"));
+  let tag_size = quote! { <#tag_ty as ::structurs::StaticSize>::SIZE };
+
+  let variant_sizes = variants.iter().map(|variant| {
+    let fields = if let syn::Fields::Named(syn::FieldsNamed { named, .. }) = &variant.fields {
+      named
+    } else {
+      panic!("'StaticSize' derive macro only supports enum variants with named fields.");
+    };
+    let fields_size = static_size_named_fields(fields);
+    quote! { ::structurs::static_size_add(#tag_size, #fields_size) }
+  });
+
+  quote! {
+    ::structurs::static_size_agree(&[ #(#variant_sizes,)* ])
+  }
+}
@@ -0,0 +1,49 @@
+use structurs::{Pad, Reader, Writer};
+
+#[derive(structurs::Read, structurs::Write, Debug, Eq, PartialEq, Default)]
+pub struct TestData2
+{
+  #[le]
+  field_1: u32,
+  #[le]
+  field_2: i16,
+}
+
+#[derive(structurs::Read, structurs::Write, Default, Debug, Eq, PartialEq)]
+pub struct TestData
+{
+  #[be]
+  pub field_1: u32,
+  #[ne]
+  pub field_2: i128,
+  #[be]
+  field_3: u8,
+  #[pad(bytes = 11)]
+  pad_to_32: Pad,
+  test_data_2: TestData2,
+  #[pad]
+  another_pad: [u32; 12],
+}
+
+const DATA: TestData = TestData {
+  field_1: 510745010,
+  field_2: 101876807604715792753432598791754839769,
+  field_3: 100,
+  pad_to_32: Pad,
+  test_data_2: TestData2 {
+    field_1: 1222188209,
+    field_2: -30174,
+  },
+  another_pad: [0; 12],
+};
+
+fn main()
+{
+  // random numbers to test. Not very useful but at least doesn't throw an error.
+  let mut c = std::io::Cursor::new(Vec::new());
+  c.write_as(&DATA).unwrap();
+
+  let mut read_back = std::io::Cursor::new(c.into_inner());
+  let t = read_back.read_as::<TestData>().unwrap();
+  assert_eq!(DATA, t);
+}
@@ -0,0 +1,43 @@
+use structurs::{Reader, Writer};
+
+#[derive(structurs::Read, structurs::Write, Debug, Eq, PartialEq)]
+#[tag(u16)]
+#[be]
+pub enum Frame
+{
+  #[tag_value = 0]
+  Ping
+  {
+    #[be]
+    payload: u32,
+  },
+  #[tag_value = 1]
+  Pong
+  {
+    #[be]
+    payload: u32,
+  },
+}
+
+const PING_BYTES: [u8; 6] = [0, 0, 0, 0, 0, 42];
+const PONG_BYTES: [u8; 6] = [0, 1, 0, 0, 1, 0];
+
+fn main()
+{
+  let mut c = std::io::Cursor::new(PING_BYTES);
+  let frame = c.read_as::<Frame>().unwrap();
+  assert_eq!(Frame::Ping { payload: 42 }, frame);
+
+  let mut c = std::io::Cursor::new(PONG_BYTES);
+  let frame = c.read_as::<Frame>().unwrap();
+  assert_eq!(Frame::Pong { payload: 256 }, frame);
+
+  let mut out = std::io::Cursor::new(Vec::new());
+  out.write_as(&Frame::Pong { payload: 256 }).unwrap();
+  assert_eq!(PONG_BYTES.to_vec(), out.into_inner());
+
+  // An unrecognized tag value is reported as `io::ErrorKind::InvalidData`.
+  let mut c = std::io::Cursor::new([0, 2, 0, 0, 0, 0]);
+  let err = c.read_as::<Frame>().unwrap_err();
+  assert_eq!(std::io::ErrorKind::InvalidData, err.kind());
+}
@@ -0,0 +1,30 @@
+use structurs::{Reader, Writer};
+
+#[derive(structurs::Read, structurs::Write, Debug, Eq, PartialEq)]
+pub struct Records
+{
+  #[be]
+  header_len: u16,
+  #[count(header_len)]
+  #[be]
+  records: Vec<u32>,
+}
+
+const DATA_BYTES: [u8; 14] = [0, 3, 0, 0, 0, 1, 0, 0, 0, 2, 0, 0, 0, 3];
+
+fn main()
+{
+  let mut c = std::io::Cursor::new(DATA_BYTES);
+  let records = c.read_as::<Records>().unwrap();
+  assert_eq!(
+    Records {
+      header_len: 3,
+      records: vec![1, 2, 3],
+    },
+    records
+  );
+
+  let mut out = std::io::Cursor::new(Vec::new());
+  out.write_as(&records).unwrap();
+  assert_eq!(DATA_BYTES.to_vec(), out.into_inner());
+}
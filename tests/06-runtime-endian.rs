@@ -0,0 +1,40 @@
+use structurs::{Endian, Read, Reader, Write, Writer};
+
+#[derive(structurs::Read, structurs::Write, Debug, Eq, PartialEq)]
+pub struct Message
+{
+  #[endian]
+  payload: u32,
+}
+
+const LE_BYTES: [u8; 4] = [1, 0, 0, 0];
+const BE_BYTES: [u8; 4] = [0, 0, 0, 1];
+
+fn main()
+{
+  let mut c = std::io::Cursor::new(LE_BYTES);
+  let msg = Message::read_with_endian(&mut c, Endian::Little).unwrap();
+  assert_eq!(Message { payload: 1 }, msg);
+
+  let mut c = std::io::Cursor::new(BE_BYTES);
+  let msg = Message::read_with_endian(&mut c, Endian::Big).unwrap();
+  assert_eq!(Message { payload: 1 }, msg);
+
+  // Plain `read` falls back to native-endian for `#[endian]` fields (most CPUs are little-endian).
+  let mut c = std::io::Cursor::new(LE_BYTES);
+  let msg = c.read_as::<Message>().unwrap();
+  assert_eq!(Message { payload: 1 }, msg);
+
+  let mut out = std::io::Cursor::new(Vec::new());
+  msg.write_with_endian(&mut out, Endian::Little).unwrap();
+  assert_eq!(LE_BYTES.to_vec(), out.into_inner());
+
+  let mut out = std::io::Cursor::new(Vec::new());
+  msg.write_with_endian(&mut out, Endian::Big).unwrap();
+  assert_eq!(BE_BYTES.to_vec(), out.into_inner());
+
+  // Plain `write` falls back to native-endian for `#[endian]` fields, matching plain `read`.
+  let mut out = std::io::Cursor::new(Vec::new());
+  out.write_as(&msg).unwrap();
+  assert_eq!(LE_BYTES.to_vec(), out.into_inner());
+}
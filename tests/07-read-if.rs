@@ -0,0 +1,45 @@
+use structurs::{Reader, Writer};
+
+#[derive(structurs::Read, structurs::Write, Debug, Eq, PartialEq)]
+pub struct Packet
+{
+  #[be]
+  has_extra: u8,
+  #[read_if(has_extra != 0)]
+  #[be]
+  extra: Option<u32>,
+}
+
+const WITH_EXTRA_BYTES: [u8; 5] = [1, 0, 0, 0, 42];
+const WITHOUT_EXTRA_BYTES: [u8; 1] = [0];
+
+fn main()
+{
+  let mut c = std::io::Cursor::new(WITH_EXTRA_BYTES);
+  let packet = c.read_as::<Packet>().unwrap();
+  assert_eq!(
+    Packet {
+      has_extra: 1,
+      extra: Some(42),
+    },
+    packet
+  );
+
+  let mut out = std::io::Cursor::new(Vec::new());
+  out.write_as(&packet).unwrap();
+  assert_eq!(WITH_EXTRA_BYTES.to_vec(), out.into_inner());
+
+  let mut c = std::io::Cursor::new(WITHOUT_EXTRA_BYTES);
+  let packet = c.read_as::<Packet>().unwrap();
+  assert_eq!(
+    Packet {
+      has_extra: 0,
+      extra: None,
+    },
+    packet
+  );
+
+  let mut out = std::io::Cursor::new(Vec::new());
+  out.write_as(&packet).unwrap();
+  assert_eq!(WITHOUT_EXTRA_BYTES.to_vec(), out.into_inner());
+}
@@ -0,0 +1,52 @@
+use structurs::{Read, Reader, Write, Writer};
+
+#[derive(structurs::Read, structurs::Write, Debug, Eq, PartialEq)]
+pub struct Record
+{
+  #[be]
+  id: u16,
+  #[align = 8]
+  _align: structurs::Pad,
+  #[be]
+  value: u32,
+  #[pad_after = 4]
+  _gap: structurs::Pad,
+  #[be]
+  tail: u16,
+}
+
+const DATA_BYTES: [u8; 18] = [
+  0, 1, // id
+  0, 0, 0, 0, 0, 0, // align padding up to offset 8
+  0, 0, 0, 42, // value
+  0, 0, 0, 0, // pad_after
+  0, 7, // tail
+];
+
+fn main()
+{
+  let mut c = std::io::Cursor::new(DATA_BYTES);
+  let record = Record::read_seek(&mut c).unwrap();
+  assert_eq!(
+    Record {
+      id: 1,
+      _align: structurs::Pad,
+      value: 42,
+      _gap: structurs::Pad,
+      tail: 7,
+    },
+    record
+  );
+  assert_eq!(DATA_BYTES.len() as u64, c.position());
+
+  let mut out = std::io::Cursor::new(Vec::new());
+  record.write_seek(&mut out).unwrap();
+  assert_eq!(DATA_BYTES.to_vec(), out.into_inner());
+
+  // Plain `read`/`write` have no `Seek` bound and so can't honor the seek directives; they must
+  // fail rather than silently desync the stream.
+  let mut c = std::io::Cursor::new(DATA_BYTES);
+  assert!(c.read_as::<Record>().is_err());
+  let mut out = std::io::Cursor::new(Vec::new());
+  assert!(out.write_as(&record).is_err());
+}
@@ -0,0 +1,48 @@
+use structurs::{Reader, Writer};
+
+// A 4-bit version followed by a 12-bit length, packed MSB-first into 2 bytes, followed by a
+// plain byte field.
+#[derive(structurs::Read, structurs::Write, Debug, Eq, PartialEq)]
+pub struct Header
+{
+  #[bits = 4]
+  version: u8,
+  #[bits = 12]
+  length: u16,
+  flags: u8,
+}
+
+const DATA_BYTES: [u8; 3] = [0b0001_0000, 0b0000_0101, 0xff];
+
+// The bit reader/writer accumulate into a u64, so a #[bits] field wider than that must be
+// rejected rather than silently truncated.
+#[derive(structurs::Read, structurs::Write, Debug, Eq, PartialEq)]
+pub struct TooWide
+{
+  #[bits = 100]
+  value: u128,
+}
+
+fn main()
+{
+  let mut c = std::io::Cursor::new(DATA_BYTES);
+  let header = c.read_as::<Header>().unwrap();
+  assert_eq!(
+    Header {
+      version: 1,
+      length: 5,
+      flags: 0xff,
+    },
+    header
+  );
+
+  let mut out = std::io::Cursor::new(Vec::new());
+  out.write_as(&header).unwrap();
+  assert_eq!(DATA_BYTES.to_vec(), out.into_inner());
+
+  let mut c = std::io::Cursor::new([0u8; 16]);
+  assert!(c.read_as::<TooWide>().is_err());
+
+  let mut out = std::io::Cursor::new(Vec::new());
+  assert!(out.write_as(&TooWide { value: 0 }).is_err());
+}
@@ -0,0 +1,48 @@
+use structurs::StaticSize;
+
+#[derive(structurs::Read, structurs::Write, structurs::StaticSize, Debug, Eq, PartialEq)]
+pub struct Nested
+{
+  #[be]
+  a: u16,
+  #[pad(bytes = 2)]
+  _pad: structurs::Pad,
+}
+
+#[derive(structurs::Read, structurs::Write, structurs::StaticSize, Debug, Eq, PartialEq)]
+pub struct Fixed
+{
+  #[be]
+  header: u32,
+  array: [u8; 3],
+  nested: Nested,
+}
+
+#[derive(structurs::Read, structurs::Write, structurs::StaticSize, Debug, Eq, PartialEq)]
+pub struct Dynamic
+{
+  #[be]
+  header_len: u16,
+  #[count(header_len)]
+  #[be]
+  records: Vec<u32>,
+}
+
+// StaticSize derived on its own, without Read/Write alongside it, so the endian attributes it
+// doesn't itself use still have to be declared in its own `attributes(..)` list.
+#[derive(structurs::StaticSize)]
+pub struct StandaloneEndian
+{
+  #[be]
+  a: u32,
+  #[le]
+  b: u16,
+}
+
+fn main()
+{
+  assert_eq!(Some(4), Nested::SIZE);
+  assert_eq!(Some(4 + 3 + 4), Fixed::SIZE);
+  assert_eq!(None, Dynamic::SIZE);
+  assert_eq!(Some(4 + 2), StandaloneEndian::SIZE);
+}